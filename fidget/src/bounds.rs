@@ -0,0 +1,203 @@
+//! Axis-aligned bounding-box estimation via interval evaluation
+use crate::{eval::types::Interval, shape::EzShape, shapes::Vec3, vm::VmShape};
+
+/// Axis-aligned bounding box
+#[derive(Copy, Clone, Debug)]
+pub struct Aabb {
+    /// Lower corner of the box
+    pub min: Vec3,
+    /// Upper corner of the box
+    pub max: Vec3,
+}
+
+/// Default half-size of the root cube searched for a bound
+pub const DEFAULT_ROOT_RADIUS: f64 = 1e3;
+
+/// Default subdivision depth limit
+pub const DEFAULT_MAX_DEPTH: usize = 12;
+
+/// Estimate the solid-region bound of `shape`, using the default root size
+/// and depth limit
+pub fn bounds(shape: &VmShape) -> Option<Aabb> {
+    bounds_in(shape, DEFAULT_ROOT_RADIUS, DEFAULT_MAX_DEPTH)
+}
+
+/// Estimate the solid-region bound of `shape`
+///
+/// This recursively subdivides a cube of half-size `root_radius` (centered
+/// on the origin), running the crate's interval evaluator on each cell:
+/// cells whose interval lower bound is strictly positive (outside the
+/// surface) are pruned, cells whose interval upper bound is at or below
+/// zero (entirely inside) are kept whole, and cells that straddle zero are
+/// subdivided down to `max_depth`. The kept inside cells and the
+/// straddling leaf cells are unioned together to form the final box.
+/// Returns `None` if every cell is pruned, i.e. no part of the root cube
+/// is inside or on the surface.
+pub fn bounds_in(
+    shape: &VmShape,
+    root_radius: f64,
+    max_depth: usize,
+) -> Option<Aabb> {
+    let tape = shape.ez_interval_tape();
+    let mut eval = VmShape::new_interval_eval();
+    let mut eval_cell = |cell: Aabb| -> Interval {
+        let x = Interval::new(cell.min.x as f32, cell.max.x as f32);
+        let y = Interval::new(cell.min.y as f32, cell.max.y as f32);
+        let z = Interval::new(cell.min.z as f32, cell.max.z as f32);
+        eval.eval(&tape, x, y, z).unwrap().0
+    };
+
+    let root = Aabb {
+        min: Vec3 {
+            x: -root_radius,
+            y: -root_radius,
+            z: -root_radius,
+        },
+        max: Vec3 {
+            x: root_radius,
+            y: root_radius,
+            z: root_radius,
+        },
+    };
+
+    let mut stack = vec![(root, max_depth)];
+    let mut result: Option<Aabb> = None;
+    while let Some((cell, depth)) = stack.pop() {
+        let i = eval_cell(cell);
+        if i.lower() > 0.0 {
+            continue; // entirely outside the surface
+        }
+        if depth == 0 || i.upper() <= 0.0 {
+            result = Some(match result {
+                Some(acc) => union(acc, cell),
+                None => cell,
+            });
+            continue;
+        }
+        for child in octants(cell) {
+            stack.push((child, depth - 1));
+        }
+    }
+    result
+}
+
+/// Pad an AABB by a fixed radius on every axis
+///
+/// This is a convenience for shapes wrapped in a [`Round`](crate::shapes::Round)
+/// or [`Onion`](crate::shapes::Onion), whose surface extends beyond the
+/// underlying shape's bound by a fixed offset.
+pub fn padded(aabb: Aabb, radius: f64) -> Aabb {
+    Aabb {
+        min: Vec3 {
+            x: aabb.min.x - radius,
+            y: aabb.min.y - radius,
+            z: aabb.min.z - radius,
+        },
+        max: Vec3 {
+            x: aabb.max.x + radius,
+            y: aabb.max.y + radius,
+            z: aabb.max.z + radius,
+        },
+    }
+}
+
+fn union(a: Aabb, b: Aabb) -> Aabb {
+    Aabb {
+        min: Vec3 {
+            x: a.min.x.min(b.min.x),
+            y: a.min.y.min(b.min.y),
+            z: a.min.z.min(b.min.z),
+        },
+        max: Vec3 {
+            x: a.max.x.max(b.max.x),
+            y: a.max.y.max(b.max.y),
+            z: a.max.z.max(b.max.z),
+        },
+    }
+}
+
+fn octants(cell: Aabb) -> [Aabb; 8] {
+    let mx = (cell.min.x + cell.max.x) * 0.5;
+    let my = (cell.min.y + cell.max.y) * 0.5;
+    let mz = (cell.min.z + cell.max.z) * 0.5;
+    let mut out = [cell; 8];
+    for (i, o) in out.iter_mut().enumerate() {
+        let (xlo, xhi) = if i & 1 == 0 {
+            (cell.min.x, mx)
+        } else {
+            (mx, cell.max.x)
+        };
+        let (ylo, yhi) = if i & 2 == 0 {
+            (cell.min.y, my)
+        } else {
+            (my, cell.max.y)
+        };
+        let (zlo, zhi) = if i & 4 == 0 {
+            (cell.min.z, mz)
+        } else {
+            (mz, cell.max.z)
+        };
+        *o = Aabb {
+            min: Vec3 {
+                x: xlo,
+                y: ylo,
+                z: zlo,
+            },
+            max: Vec3 {
+                x: xhi,
+                y: yhi,
+                z: zhi,
+            },
+        };
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{context::Tree, shapes::Sphere};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn sphere_bounds() {
+        let sphere = Sphere {
+            center: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            radius: 1.0,
+        };
+        let shape = VmShape::from(Tree::from(sphere));
+        // Size the root cube and subdivision depth to the shape under
+        // test, rather than `bounds`'s scene-scale defaults: resolution
+        // is `2 * root_radius / 2^max_depth`, and `DEFAULT_ROOT_RADIUS`
+        // with `DEFAULT_MAX_DEPTH` only reaches ~0.5 units of precision,
+        // far coarser than this unit sphere's radius.
+        let aabb = bounds_in(&shape, 2.0, 10).unwrap();
+        assert_relative_eq!(aabb.min.x, -1.0, epsilon = 0.05);
+        assert_relative_eq!(aabb.max.x, 1.0, epsilon = 0.05);
+        assert_relative_eq!(aabb.min.y, -1.0, epsilon = 0.05);
+        assert_relative_eq!(aabb.max.y, 1.0, epsilon = 0.05);
+    }
+
+    #[test]
+    fn padded_bounds() {
+        let aabb = Aabb {
+            min: Vec3 {
+                x: -1.0,
+                y: -1.0,
+                z: -1.0,
+            },
+            max: Vec3 {
+                x: 1.0,
+                y: 1.0,
+                z: 1.0,
+            },
+        };
+        let grown = padded(aabb, 0.5);
+        assert_relative_eq!(grown.min.x, -1.5);
+        assert_relative_eq!(grown.max.x, 1.5);
+    }
+}
@@ -0,0 +1,5 @@
+//! Fidget: a library for generating and rendering implicit surfaces
+pub mod shapes;
+
+pub mod raytrace;
+pub mod bounds;
@@ -0,0 +1,204 @@
+//! Sphere-tracing ray / shape intersection
+//!
+//! This module marches a [`Ray`] against a compiled [`VmShape`], stopping
+//! when the signed distance drops below an epsilon (a hit) or the ray has
+//! travelled past a maximum range (a miss).
+use crate::{shape::EzShape, shapes::Vec3, vm::VmShape};
+
+/// A ray in 3D space
+#[derive(Copy, Clone, Debug)]
+pub struct Ray {
+    /// Ray origin
+    pub origin: Vec3,
+    /// Ray direction (should be normalized)
+    pub dir: Vec3,
+}
+
+/// Result of a successful ray / shape intersection
+#[derive(Copy, Clone, Debug)]
+pub struct Hit {
+    /// World-space point where the ray hit the surface
+    pub point: Vec3,
+    /// Distance along the ray at which the hit occurred
+    pub t: f64,
+    /// Surface normal at the hit point, estimated by central differences
+    pub normal: Vec3,
+}
+
+/// Default epsilon used to detect a surface hit
+pub const DEFAULT_EPSILON: f64 = 1e-4;
+
+/// Default maximum ray distance before declaring a miss
+pub const DEFAULT_MAX_T: f64 = 1e3;
+
+/// Default maximum number of sphere-tracing steps
+pub const DEFAULT_MAX_STEPS: usize = 256;
+
+/// March `ray` against `shape` using sphere tracing
+///
+/// Starting at `t = 0`, this repeatedly evaluates the shape's signed
+/// distance at `ray.origin + ray.dir * t` and advances `t` by that
+/// distance. Tracing stops with a hit once the distance falls below
+/// `epsilon`, or with a miss once `t` exceeds `max_t` or `max_steps`
+/// iterations have elapsed.
+pub fn sphere_trace(
+    shape: &VmShape,
+    ray: Ray,
+    epsilon: f64,
+    max_t: f64,
+    max_steps: usize,
+) -> Option<Hit> {
+    let tape = shape.ez_point_tape();
+    let mut eval = VmShape::new_point_eval();
+    let mut eval_at = |p: Vec3| -> f64 {
+        eval.eval(&tape, p.x as f32, p.y as f32, p.z as f32)
+            .unwrap()
+            .0 as f64
+    };
+
+    let mut t = 0.0;
+    for _ in 0..max_steps {
+        let p = point_at(ray, t);
+        let d = eval_at(p);
+        if d < epsilon {
+            let normal = estimate_normal(&mut eval_at, p, epsilon);
+            return Some(Hit { point: p, t, normal });
+        }
+        t += d;
+        if t > max_t {
+            return None;
+        }
+    }
+    None
+}
+
+fn point_at(ray: Ray, t: f64) -> Vec3 {
+    Vec3 {
+        x: ray.origin.x + ray.dir.x * t,
+        y: ray.origin.y + ray.dir.y * t,
+        z: ray.origin.z + ray.dir.z * t,
+    }
+}
+
+/// Estimate the surface normal at `p` using central differences
+fn estimate_normal(
+    eval_at: &mut impl FnMut(Vec3) -> f64,
+    p: Vec3,
+    epsilon: f64,
+) -> Vec3 {
+    let dx = eval_at(Vec3 {
+        x: p.x + epsilon,
+        y: p.y,
+        z: p.z,
+    }) - eval_at(Vec3 {
+        x: p.x - epsilon,
+        y: p.y,
+        z: p.z,
+    });
+    let dy = eval_at(Vec3 {
+        x: p.x,
+        y: p.y + epsilon,
+        z: p.z,
+    }) - eval_at(Vec3 {
+        x: p.x,
+        y: p.y - epsilon,
+        z: p.z,
+    });
+    let dz = eval_at(Vec3 {
+        x: p.x,
+        y: p.y,
+        z: p.z + epsilon,
+    }) - eval_at(Vec3 {
+        x: p.x,
+        y: p.y,
+        z: p.z - epsilon,
+    });
+    let len = (dx * dx + dy * dy + dz * dz).sqrt();
+    if len == 0.0 {
+        Vec3 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        }
+    } else {
+        Vec3 {
+            x: dx / len,
+            y: dy / len,
+            z: dz / len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{context::Tree, shapes::Sphere};
+
+    #[test]
+    fn sphere_hit() {
+        let sphere = Sphere {
+            center: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            radius: 1.0,
+        };
+        let shape = VmShape::from(Tree::from(sphere));
+        let ray = Ray {
+            origin: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            dir: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        let hit = sphere_trace(
+            &shape,
+            ray,
+            DEFAULT_EPSILON,
+            DEFAULT_MAX_T,
+            DEFAULT_MAX_STEPS,
+        )
+        .unwrap();
+        assert!((hit.t - 4.0).abs() < 1e-3);
+        assert!(hit.normal.z < -0.99);
+    }
+
+    #[test]
+    fn sphere_miss() {
+        let sphere = Sphere {
+            center: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            radius: 1.0,
+        };
+        let shape = VmShape::from(Tree::from(sphere));
+        let ray = Ray {
+            origin: Vec3 {
+                x: 5.0,
+                y: 5.0,
+                z: -5.0,
+            },
+            dir: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+        };
+        assert!(sphere_trace(
+            &shape,
+            ray,
+            DEFAULT_EPSILON,
+            DEFAULT_MAX_T,
+            DEFAULT_MAX_STEPS,
+        )
+        .is_none());
+    }
+}
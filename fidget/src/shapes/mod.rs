@@ -45,6 +45,33 @@ impl From<Rect> for Tree {
     }
 }
 
+/// 2D line segment, rounded into a capsule shape
+#[derive(Clone, Facet)]
+pub struct Segment {
+    /// One endpoint of the segment
+    pub a: Vec2,
+    /// Other endpoint of the segment
+    pub b: Vec2,
+    /// Radius of the segment
+    pub radius: f64,
+}
+
+impl From<Segment> for Tree {
+    fn from(v: Segment) -> Self {
+        let (x, y, _) = Tree::axes();
+        let pax = x - v.a.x;
+        let pay = y - v.a.y;
+        let bax = v.b.x - v.a.x;
+        let bay = v.b.y - v.a.y;
+        let dot_ba = bax * bax + bay * bay;
+        let dot_pa_ba = pax.clone() * bax + pay.clone() * bay;
+        let h = (dot_pa_ba / dot_ba).max(0.0).min(1.0);
+        let dx = pax - h.clone() * bax;
+        let dy = pay - h * bay;
+        (dx.square() + dy.square()).sqrt() - v.radius
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // 3D shapes
 
@@ -139,6 +166,37 @@ impl From<Torus> for Tree {
     }
 }
 
+/// Capsule (a line segment rounded into a rod) between two points in 3D
+#[derive(Clone, Facet)]
+pub struct Capsule {
+    /// One endpoint of the capsule's axis
+    pub a: Vec3,
+    /// Other endpoint of the capsule's axis
+    pub b: Vec3,
+    /// Radius of the capsule
+    pub radius: f64,
+}
+
+impl From<Capsule> for Tree {
+    fn from(v: Capsule) -> Self {
+        let (x, y, z) = Tree::axes();
+        let pax = x - v.a.x;
+        let pay = y - v.a.y;
+        let paz = z - v.a.z;
+        let bax = v.b.x - v.a.x;
+        let bay = v.b.y - v.a.y;
+        let baz = v.b.z - v.a.z;
+        let dot_ba = bax * bax + bay * bay + baz * baz;
+        let dot_pa_ba =
+            pax.clone() * bax + pay.clone() * bay + paz.clone() * baz;
+        let h = (dot_pa_ba / dot_ba).max(0.0).min(1.0);
+        let dx = pax - h.clone() * bax;
+        let dy = pay - h.clone() * bay;
+        let dz = paz - h * baz;
+        (dx.square() + dy.square() + dz.square()).sqrt() - v.radius
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // CSG operations
 
@@ -222,6 +280,98 @@ impl From<Difference> for Tree {
     }
 }
 
+/// Polynomial smooth minimum of `a` and `b`, blending over radius `k`
+///
+/// Negating `k` turns this into a smooth maximum, which is how the smooth
+/// CSG operators below build smooth intersection and difference out of the
+/// same polynomial.
+fn smooth_min(a: Tree, b: Tree, k: f64) -> Tree {
+    let h = (((b.clone() - a.clone()) / k) * 0.5 + 0.5).max(0.0).min(1.0);
+    let mix = b.clone() + (a - b) * h.clone();
+    mix - (h.clone() - h.square()) * k
+}
+
+/// Take the smooth union of a set of shapes, blending creases with radius `k`
+///
+/// If the input is empty, returns an constant empty tree (at +∞)
+#[derive(Clone, Facet)]
+pub struct SmoothUnion {
+    /// List of shapes to merge
+    pub input: Vec<Tree>,
+    /// Blend radius
+    pub k: f64,
+}
+
+impl From<SmoothUnion> for Tree {
+    fn from(v: SmoothUnion) -> Self {
+        if v.input.is_empty() {
+            // XXX should this be an error instead?
+            Tree::constant(f64::INFINITY)
+        } else {
+            fn recurse(s: &[Tree], k: f64) -> Tree {
+                match s.len() {
+                    1 => s[0].clone(),
+                    n => smooth_min(
+                        recurse(&s[..n / 2], k),
+                        recurse(&s[n / 2..], k),
+                        k,
+                    ),
+                }
+            }
+            recurse(&v.input, v.k)
+        }
+    }
+}
+
+/// Take the smooth intersection of a set of shapes, blending creases with radius `k`
+///
+/// If the input is empty, returns a constant full tree (at -∞)
+#[derive(Clone, Facet)]
+pub struct SmoothIntersection {
+    /// List of shapes to intersect
+    pub input: Vec<Tree>,
+    /// Blend radius
+    pub k: f64,
+}
+
+impl From<SmoothIntersection> for Tree {
+    fn from(v: SmoothIntersection) -> Self {
+        if v.input.is_empty() {
+            // XXX should this be an error instead?
+            Tree::constant(-f64::INFINITY)
+        } else {
+            fn recurse(s: &[Tree], k: f64) -> Tree {
+                match s.len() {
+                    1 => s[0].clone(),
+                    n => smooth_min(
+                        recurse(&s[..n / 2], k),
+                        recurse(&s[n / 2..], k),
+                        -k,
+                    ),
+                }
+            }
+            recurse(&v.input, v.k)
+        }
+    }
+}
+
+/// Take the smooth difference of two shapes, blending the crease with radius `k`
+#[derive(Clone, Facet)]
+pub struct SmoothDifference {
+    /// Original shape
+    pub shape: Tree,
+    /// Shape to be subtracted from the original
+    pub cutout: Tree,
+    /// Blend radius
+    pub k: f64,
+}
+
+impl From<SmoothDifference> for Tree {
+    fn from(v: SmoothDifference) -> Self {
+        smooth_min(v.shape, -v.cutout, -v.k)
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Morphological operations
 
@@ -298,6 +448,46 @@ impl From<Twist> for Tree {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// 2D-to-3D operations
+
+/// Extrude a 2D shape along the Z axis, giving it thickness
+#[derive(Clone, Facet)]
+pub struct Extrude {
+    /// 2D shape to extrude (evaluated in the XY plane)
+    pub shape: Tree,
+    /// Half-height of the extrusion along Z
+    pub half_height: f64,
+}
+
+impl From<Extrude> for Tree {
+    fn from(v: Extrude) -> Self {
+        let (_, _, z) = Tree::axes();
+        let d = v.shape;
+        let w = z.abs() - v.half_height;
+        let od = d.clone().max(0.0);
+        let ow = w.clone().max(0.0);
+        (od.square() + ow.square()).sqrt() + d.max(w).min(0.0)
+    }
+}
+
+/// Revolve a 2D profile around the Y axis, producing a solid of revolution
+#[derive(Clone, Facet)]
+pub struct Revolve {
+    /// 2D profile to revolve (evaluated in the XY plane)
+    pub shape: Tree,
+    /// Offset of the profile from the axis of revolution
+    pub offset: f64,
+}
+
+impl From<Revolve> for Tree {
+    fn from(v: Revolve) -> Self {
+        let (x, y, z) = Tree::axes();
+        let rx = (x.square() + z.square()).sqrt() - v.offset;
+        v.shape.remap_xyz(rx, y, Tree::constant(0.0))
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Transforms
 
@@ -342,6 +532,32 @@ impl From<Scale> for Tree {
     }
 }
 
+/// Rotate a shape around an arbitrary axis
+#[derive(Clone, Facet)]
+pub struct Rotate {
+    /// Shape to rotate
+    pub shape: Tree,
+    /// Axis of rotation (need not be normalized)
+    pub axis: Vec3,
+    /// Angle of rotation, in radians
+    pub angle: f64,
+}
+
+impl From<Rotate> for Tree {
+    fn from(v: Rotate) -> Self {
+        let axis = nalgebra::Unit::new_normalize(nalgebra::Vector3::new(
+            v.axis.x, v.axis.y, v.axis.z,
+        ));
+        let rot = nalgebra::UnitQuaternion::from_axis_angle(&axis, v.angle);
+        v.shape.remap_affine(nalgebra::convert(
+            nalgebra::Isometry3::from_parts(
+                nalgebra::Translation3::identity(),
+                rot.inverse(),
+            ),
+        ))
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
@@ -360,6 +576,14 @@ mod test {
         assert_eq!(Rect::SHAPE.doc, &[" Axis-aligned rectangle"]);
     }
 
+    #[test]
+    fn segment_docstring() {
+        assert_eq!(
+            Segment::SHAPE.doc,
+            &[" 2D line segment, rounded into a capsule shape"]
+        );
+    }
+
     #[test]
     fn cuboid_docstring() {
         assert_eq!(Cuboid::SHAPE.doc, &[" Axis-aligned box"]);
@@ -373,6 +597,111 @@ mod test {
         );
     }
 
+    #[test]
+    fn capsule_docstring() {
+        assert_eq!(
+            Capsule::SHAPE.doc,
+            &[" Capsule (a line segment rounded into a rod) between two points in 3D"]
+        );
+    }
+
+    #[test]
+    fn smooth_union_docstring() {
+        assert_eq!(
+            SmoothUnion::SHAPE.doc[0],
+            " Take the smooth union of a set of shapes, blending creases with radius `k`"
+        );
+    }
+
+    #[test]
+    fn smooth_intersection_docstring() {
+        assert_eq!(
+            SmoothIntersection::SHAPE.doc[0],
+            " Take the smooth intersection of a set of shapes, blending creases with radius `k`"
+        );
+    }
+
+    #[test]
+    fn smooth_difference_docstring() {
+        assert_eq!(
+            SmoothDifference::SHAPE.doc,
+            &[" Take the smooth difference of two shapes, blending the crease with radius `k`"]
+        );
+    }
+
+    #[test]
+    fn smooth_union_sdf() {
+        // Two spheres, equidistant from the midplane, so the hard union
+        // and both inputs agree there: hard min == a == b.
+        let a = Sphere {
+            center: Vec3 {
+                x: -2.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            radius: 1.0,
+        };
+        let b = Sphere {
+            center: Vec3 {
+                x: 2.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            radius: 1.0,
+        };
+        let k = 0.5;
+        let hard = Union {
+            input: vec![Tree::from(a.clone()), Tree::from(b.clone())],
+        };
+        let smooth = SmoothUnion {
+            input: vec![Tree::from(a), Tree::from(b)],
+            k,
+        };
+        let hard_shape = VmShape::from(Tree::from(hard));
+        let hard_tape = hard_shape.ez_point_tape();
+        let mut hard_eval = VmShape::new_point_eval();
+        let hard_val = hard_eval.eval(&hard_tape, 0.0, 0.0, 0.0).unwrap().0;
+
+        let smooth_shape = VmShape::from(Tree::from(smooth));
+        let smooth_tape = smooth_shape.ez_point_tape();
+        let mut smooth_eval = VmShape::new_point_eval();
+        let smooth_val =
+            smooth_eval.eval(&smooth_tape, 0.0, 0.0, 0.0).unwrap().0;
+
+        // At the symmetric midpoint, the polynomial smooth-min reads
+        // exactly `k / 4` below the hard minimum.
+        assert_relative_eq!(hard_val, 1.0);
+        assert_relative_eq!(smooth_val, hard_val - (k / 4.0) as f32);
+    }
+
+    #[test]
+    fn smooth_difference_sdf() {
+        // Subtracting a shape from an identical copy of itself: on the
+        // shared surface both the shape and its (negated) cutout read 0,
+        // so the blend reads exactly `k / 4` above the hard difference.
+        let sphere = Sphere {
+            center: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            radius: 1.5,
+        };
+        let k = 0.5;
+        let smooth = SmoothDifference {
+            shape: Tree::from(sphere.clone()),
+            cutout: Tree::from(sphere),
+            k,
+        };
+        let shape = VmShape::from(Tree::from(smooth));
+        let tape = shape.ez_point_tape();
+        let mut eval = VmShape::new_point_eval();
+        assert_relative_eq!(
+            eval.eval(&tape, 1.5, 0.0, 0.0).unwrap().0,
+            (k / 4.0) as f32
+        );
+    }
+
     #[test]
     fn torus_docstring() {
         assert_eq!(Torus::SHAPE.doc, &[" Torus aligned with the Y axis"]);
@@ -416,6 +745,21 @@ mod test {
         assert_relative_eq!(eval.eval(&tape, 0.0, 3.0, 0.0).unwrap().0, 1.0);
     }
 
+    #[test]
+    fn segment_sdf() {
+        let segment = Segment {
+            a: Vec2 { x: -1.0, y: 0.0 },
+            b: Vec2 { x: 1.0, y: 0.0 },
+            radius: 0.5,
+        };
+        let shape = VmShape::from(Tree::from(segment));
+        let tape = shape.ez_point_tape();
+        let mut eval = VmShape::new_point_eval();
+        assert_relative_eq!(eval.eval(&tape, 0.0, 0.0, 0.0).unwrap().0, -0.5);
+        assert_relative_eq!(eval.eval(&tape, 0.0, 0.5, 0.0).unwrap().0, 0.0);
+        assert_relative_eq!(eval.eval(&tape, 2.0, 0.0, 0.0).unwrap().0, 0.5);
+    }
+
     #[test]
     fn cuboid_sdf() {
         let cuboid = Cuboid {
@@ -461,6 +805,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn capsule_sdf() {
+        let capsule = Capsule {
+            a: Vec3 {
+                x: -1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            b: Vec3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            radius: 0.5,
+        };
+        let shape = VmShape::from(Tree::from(capsule));
+        let tape = shape.ez_point_tape();
+        let mut eval = VmShape::new_point_eval();
+        assert_relative_eq!(eval.eval(&tape, 0.0, 0.0, 0.0).unwrap().0, -0.5);
+        assert_relative_eq!(eval.eval(&tape, 0.0, 0.5, 0.0).unwrap().0, 0.0);
+        assert_relative_eq!(eval.eval(&tape, 2.0, 0.0, 0.0).unwrap().0, 0.5);
+    }
+
     #[test]
     fn torus_sdf_values() {
         let torus = Torus {
@@ -551,4 +918,103 @@ mod test {
     fn twist_docstring() {
         assert_eq!(Twist::SHAPE.doc, &[" Twist a shape around the Y axis"]);
     }
+
+    #[test]
+    fn extrude_docstring() {
+        assert_eq!(
+            Extrude::SHAPE.doc,
+            &[" Extrude a 2D shape along the Z axis, giving it thickness"]
+        );
+    }
+
+    #[test]
+    fn revolve_docstring() {
+        assert_eq!(
+            Revolve::SHAPE.doc,
+            &[" Revolve a 2D profile around the Y axis, producing a solid of revolution"]
+        );
+    }
+
+    #[test]
+    fn rotate_docstring() {
+        assert_eq!(
+            Rotate::SHAPE.doc,
+            &[" Rotate a shape around an arbitrary axis"]
+        );
+    }
+
+    #[test]
+    fn rotate_sdf() {
+        // An asymmetric box (distinct half-sizes on X and Y), rotated 90
+        // degrees about Z, so that its X and Y extents swap: the box now
+        // spans x in [-2, 2] and y in [-1, 1].
+        let cuboid = Cuboid {
+            center: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            half_size: Vec3 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            },
+        };
+        let rotate = Rotate {
+            shape: Tree::from(cuboid).into(),
+            axis: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            angle: std::f64::consts::FRAC_PI_2,
+        };
+        let shape = VmShape::from(Tree::from(rotate));
+        let tape = shape.ez_point_tape();
+        let mut eval = VmShape::new_point_eval();
+        // On the new (swapped) X surface
+        assert_relative_eq!(eval.eval(&tape, 2.0, 0.0, 0.0).unwrap().0, 0.0);
+        // Outside the new (narrower) Y extent
+        assert_relative_eq!(eval.eval(&tape, 0.0, 2.0, 0.0).unwrap().0, 1.0);
+        // Inside along the new (wider) X extent
+        assert_relative_eq!(eval.eval(&tape, 1.0, 0.0, 0.0).unwrap().0, -1.0);
+    }
+
+    #[test]
+    fn extrude_sdf() {
+        let circle = Circle {
+            center: Vec2 { x: 0.0, y: 0.0 },
+            radius: 1.0,
+        };
+        let extrude = Extrude {
+            shape: Tree::from(circle).into(),
+            half_height: 2.0,
+        };
+        let shape = VmShape::from(Tree::from(extrude));
+        let tape = shape.ez_point_tape();
+        let mut eval = VmShape::new_point_eval();
+        assert_relative_eq!(eval.eval(&tape, 0.0, 0.0, 0.0).unwrap().0, -1.0);
+        assert_relative_eq!(eval.eval(&tape, 2.0, 0.0, 0.0).unwrap().0, 1.0);
+        assert_relative_eq!(eval.eval(&tape, 0.0, 0.0, 3.0).unwrap().0, 1.0);
+    }
+
+    #[test]
+    fn revolve_sdf() {
+        // Revolving a circle offset from the axis of revolution produces a
+        // torus (compare against `torus_sdf_values` above).
+        let circle = Circle {
+            center: Vec2 { x: 0.0, y: 0.0 },
+            radius: 1.0,
+        };
+        let revolve = Revolve {
+            shape: Tree::from(circle).into(),
+            offset: 3.0,
+        };
+        let shape = VmShape::from(Tree::from(revolve));
+        let tape = shape.ez_point_tape();
+        let mut eval = VmShape::new_point_eval();
+        assert_relative_eq!(eval.eval(&tape, 4.0, 0.0, 0.0).unwrap().0, 0.0);
+        assert_relative_eq!(eval.eval(&tape, 3.0, 0.0, 0.0).unwrap().0, -1.0);
+        assert_relative_eq!(eval.eval(&tape, 0.0, 0.0, 0.0).unwrap().0, 2.0);
+    }
 }